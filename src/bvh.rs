@@ -0,0 +1,416 @@
+//! A surface-area-heuristic bounding volume hierarchy over any collection of shapes with
+//! an axis-aligned bound, for accelerating ray and overlap queries against large scenes
+//! instead of scanning every primitive.
+//!
+//! The tree and its leaf index list are heap-allocated, so this module needs `alloc` on
+//! `no_std` targets (see the crate's `std`/`alloc` feature wiring).
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use cgmath::prelude::*;
+use cgmath::{BaseFloat, Point3};
+
+use crate::prelude::*;
+use crate::{Aabb3, Ray3};
+
+/// A node holding this many primitives or fewer is kept as a single leaf rather than split
+/// further.
+const LEAF_SIZE: usize = 4;
+
+enum NodeKind {
+    Leaf { start: usize, len: usize },
+    Internal { left: usize, right: usize },
+}
+
+struct Node<S> {
+    bound: Aabb3<S>,
+    kind: NodeKind,
+}
+
+/// A bounding volume hierarchy over a fixed set of primitives, built with the surface
+/// area heuristic.
+///
+/// `P` must implement `ComputeBound<Aabb3<S>>`, so any shape that already reports an
+/// axis-aligned bound (`Cuboid`, `Cube`, ...) plugs in directly.
+pub struct Bvh<S, P> {
+    nodes: Vec<Node<S>>,
+    primitives: Vec<P>,
+    indices: Vec<usize>,
+    root: usize,
+}
+
+impl<S, P> Bvh<S, P>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb3<S>>,
+{
+    /// Build a BVH over `primitives`.
+    pub fn new(primitives: Vec<P>) -> Self {
+        let mut entries: Vec<(Aabb3<S>, Point3<S>, usize)> = primitives
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let bound = p.compute_bound();
+                let centroid = Point3::midpoint(bound.min, bound.max);
+                (bound, centroid, i)
+            })
+            .collect();
+
+        let mut nodes = Vec::new();
+        let mut indices = Vec::with_capacity(entries.len());
+        let root = if entries.is_empty() {
+            nodes.push(Node {
+                bound: Aabb3::new(Point3::origin(), Point3::origin()),
+                kind: NodeKind::Leaf { start: 0, len: 0 },
+            });
+            0
+        } else {
+            build_recursive(&mut entries, &mut nodes, &mut indices)
+        };
+
+        Bvh {
+            nodes,
+            primitives,
+            indices,
+            root,
+        }
+    }
+
+    /// Iterate, lazily, over the primitives whose bound the ray intersects.
+    ///
+    /// Nodes whose bound the ray misses are pruned without descending into their subtree.
+    pub fn query_ray<'a>(&'a self, ray: &Ray3<S>) -> BvhRayQuery<'a, S, P> {
+        BvhRayQuery {
+            bvh: self,
+            ray: *ray,
+            stack: vec![self.root],
+            leaf: &[],
+            leaf_pos: 0,
+        }
+    }
+
+    /// Iterate, lazily, over the primitives whose bound overlaps `aabb`.
+    pub fn query_overlaps<'a>(&'a self, aabb: &Aabb3<S>) -> BvhOverlapQuery<'a, S, P> {
+        BvhOverlapQuery {
+            bvh: self,
+            aabb: *aabb,
+            stack: vec![self.root],
+            leaf: &[],
+            leaf_pos: 0,
+        }
+    }
+}
+
+fn union<S: BaseFloat>(a: &Aabb3<S>, b: &Aabb3<S>) -> Aabb3<S> {
+    Aabb3::new(
+        Point3::new(
+            a.min.x.min(b.min.x),
+            a.min.y.min(b.min.y),
+            a.min.z.min(b.min.z),
+        ),
+        Point3::new(
+            a.max.x.max(b.max.x),
+            a.max.y.max(b.max.y),
+            a.max.z.max(b.max.z),
+        ),
+    )
+}
+
+fn surface_area<S: BaseFloat>(b: &Aabb3<S>) -> S {
+    let d = b.max - b.min;
+    let two = S::one() + S::one();
+    two * (d.x * d.y + d.x * d.z + d.y * d.z)
+}
+
+fn build_recursive<S: BaseFloat>(
+    entries: &mut [(Aabb3<S>, Point3<S>, usize)],
+    nodes: &mut Vec<Node<S>>,
+    indices: &mut Vec<usize>,
+) -> usize {
+    let bound = entries
+        .iter()
+        .skip(1)
+        .fold(entries[0].0, |acc, (b, _, _)| union(&acc, b));
+
+    if entries.len() <= LEAF_SIZE {
+        return push_leaf(entries, bound, nodes, indices);
+    }
+
+    // Pick the split axis as the longest centroid extent.
+    let (centroid_min, centroid_max) = entries.iter().skip(1).fold(
+        (entries[0].1, entries[0].1),
+        |(min, max), (_, c, _)| {
+            (
+                Point3::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)),
+                Point3::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)),
+            )
+        },
+    );
+    let extent = centroid_max - centroid_min;
+    let axis = if extent.x > extent.y && extent.x > extent.z {
+        0
+    } else if extent.y > extent.z {
+        1
+    } else {
+        2
+    };
+
+    // Partition around the median centroid on the chosen axis with a quickselect
+    // (`nth_element`), so construction stays O(n log n) rather than fully sorting.
+    let mid = entries.len() / 2;
+    nth_element_by_key(entries, mid, |(_, c, _)| match axis {
+        0 => c.x,
+        1 => c.y,
+        _ => c.z,
+    });
+
+    let (left, right) = entries.split_at_mut(mid);
+    let left_bound = left
+        .iter()
+        .skip(1)
+        .fold(left[0].0, |acc, (b, _, _)| union(&acc, b));
+    let right_bound = right
+        .iter()
+        .skip(1)
+        .fold(right[0].0, |acc, (b, _, _)| union(&acc, b));
+
+    // A split only pays for itself if the combined child cost beats treating this node
+    // as one big leaf.
+    let split_cost = surface_area(&left_bound) * S::from(left.len()).unwrap()
+        + surface_area(&right_bound) * S::from(right.len()).unwrap();
+    let leaf_cost = surface_area(&bound) * S::from(entries.len()).unwrap();
+
+    if split_cost >= leaf_cost {
+        return push_leaf(entries, bound, nodes, indices);
+    }
+
+    let left_idx = build_recursive(left, nodes, indices);
+    let right_idx = build_recursive(right, nodes, indices);
+
+    nodes.push(Node {
+        bound,
+        kind: NodeKind::Internal {
+            left: left_idx,
+            right: right_idx,
+        },
+    });
+    nodes.len() - 1
+}
+
+fn push_leaf<S: BaseFloat>(
+    entries: &[(Aabb3<S>, Point3<S>, usize)],
+    bound: Aabb3<S>,
+    nodes: &mut Vec<Node<S>>,
+    indices: &mut Vec<usize>,
+) -> usize {
+    let start = indices.len();
+    indices.extend(entries.iter().map(|(_, _, i)| *i));
+    nodes.push(Node {
+        bound,
+        kind: NodeKind::Leaf {
+            start,
+            len: entries.len(),
+        },
+    });
+    nodes.len() - 1
+}
+
+/// Quickselect (`nth_element`): partitions `slice` in place so that the element at index
+/// `k` is the one that would be there if `slice` were fully sorted by `key`, without
+/// paying for a full sort.
+fn nth_element_by_key<T, K, F>(slice: &mut [T], k: usize, key: F)
+where
+    K: PartialOrd,
+    F: Fn(&T) -> K,
+{
+    if slice.len() < 2 {
+        return;
+    }
+    let mut lo = 0;
+    let mut hi = slice.len() - 1;
+    while lo < hi {
+        let pivot_index = partition(slice, lo, hi, &key);
+        if k == pivot_index {
+            return;
+        } else if k < pivot_index {
+            hi = pivot_index - 1;
+        } else {
+            lo = pivot_index + 1;
+        }
+    }
+}
+
+fn partition<T, K, F>(slice: &mut [T], lo: usize, hi: usize, key: &F) -> usize
+where
+    K: PartialOrd,
+    F: Fn(&T) -> K,
+{
+    let mid = lo + (hi - lo) / 2;
+    slice.swap(mid, hi);
+    let mut store = lo;
+    for i in lo..hi {
+        if key(&slice[i]) < key(&slice[hi]) {
+            slice.swap(i, store);
+            store += 1;
+        }
+    }
+    slice.swap(store, hi);
+    store
+}
+
+/// Lazy iterator over the primitives whose own bound the ray intersects, returned by
+/// [`Bvh::query_ray`].
+pub struct BvhRayQuery<'a, S, P> {
+    bvh: &'a Bvh<S, P>,
+    ray: Ray3<S>,
+    stack: Vec<usize>,
+    leaf: &'a [usize],
+    leaf_pos: usize,
+}
+
+impl<'a, S, P> Iterator for BvhRayQuery<'a, S, P>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb3<S>>,
+{
+    type Item = &'a P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.leaf_pos < self.leaf.len() {
+                let index = self.leaf[self.leaf_pos];
+                self.leaf_pos += 1;
+                let primitive = &self.bvh.primitives[index];
+                if primitive.compute_bound().intersects(&self.ray) {
+                    return Some(primitive);
+                }
+            }
+
+            let node = &self.bvh.nodes[self.stack.pop()?];
+            if !node.bound.intersects(&self.ray) {
+                continue;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { start, len } => {
+                    self.leaf = &self.bvh.indices[start..start + len];
+                    self.leaf_pos = 0;
+                }
+                NodeKind::Internal { left, right } => {
+                    self.stack.push(left);
+                    self.stack.push(right);
+                }
+            }
+        }
+    }
+}
+
+/// Lazy iterator over the primitives whose own bound overlaps an `Aabb3`, returned by
+/// [`Bvh::query_overlaps`].
+pub struct BvhOverlapQuery<'a, S, P> {
+    bvh: &'a Bvh<S, P>,
+    aabb: Aabb3<S>,
+    stack: Vec<usize>,
+    leaf: &'a [usize],
+    leaf_pos: usize,
+}
+
+impl<'a, S, P> Iterator for BvhOverlapQuery<'a, S, P>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb3<S>>,
+{
+    type Item = &'a P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.leaf_pos < self.leaf.len() {
+                let index = self.leaf[self.leaf_pos];
+                self.leaf_pos += 1;
+                let primitive = &self.bvh.primitives[index];
+                if primitive.compute_bound().intersects(&self.aabb) {
+                    return Some(primitive);
+                }
+            }
+
+            let node = &self.bvh.nodes[self.stack.pop()?];
+            if !node.bound.intersects(&self.aabb) {
+                continue;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { start, len } => {
+                    self.leaf = &self.bvh.indices[start..start + len];
+                    self.leaf_pos = 0;
+                }
+                NodeKind::Internal { left, right } => {
+                    self.stack.push(left);
+                    self.stack.push(right);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point3, Vector3};
+
+    use super::*;
+    use crate::primitive::Cuboid;
+    use Ray3;
+
+    fn cuboid_at(x: f32) -> (Cuboid<f32>, Aabb3<f32>) {
+        let c = Cuboid::new(1., 1., 1.);
+        let bound = Aabb3::new(Point3::new(x - 0.5, -0.5, -0.5), Point3::new(x + 0.5, 0.5, 0.5));
+        (c, bound)
+    }
+
+    struct Positioned {
+        cuboid: Cuboid<f32>,
+        bound: Aabb3<f32>,
+    }
+
+    impl ComputeBound<Aabb3<f32>> for Positioned {
+        fn compute_bound(&self) -> Aabb3<f32> {
+            self.bound
+        }
+    }
+
+    fn scene() -> Vec<Positioned> {
+        (0..10)
+            .map(|i| {
+                let (cuboid, bound) = cuboid_at(i as f32 * 3.);
+                Positioned { cuboid, bound }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_query_ray_hits_only_intersected_boxes() {
+        let bvh = Bvh::new(scene());
+        let ray = Ray3::new(Point3::new(6., 0., -10.), Vector3::new(0., 0., 1.));
+        let hits: Vec<_> = bvh.query_ray(&ray).collect();
+        assert_eq!(1, hits.len());
+        assert_eq!(6., hits[0].bound.min.x + 0.5);
+    }
+
+    #[test]
+    fn test_query_ray_misses_everything() {
+        let bvh = Bvh::new(scene());
+        let ray = Ray3::new(Point3::new(100., 0., -10.), Vector3::new(0., 0., 1.));
+        assert_eq!(0, bvh.query_ray(&ray).count());
+    }
+
+    #[test]
+    fn test_query_overlaps() {
+        let bvh = Bvh::new(scene());
+        let aabb = Aabb3::new(Point3::new(2., -1., -1.), Point3::new(4., 1., 1.));
+        let hits: Vec<_> = bvh.query_overlaps(&aabb).collect();
+        assert_eq!(1, hits.len());
+        let _ = &hits[0].cuboid;
+    }
+}
@@ -0,0 +1,19 @@
+use cgmath::prelude::*;
+use cgmath::{BaseFloat, Point3};
+
+/// Compute a bounding volume that tightly encloses `self` after it has been placed in the
+/// world by `transform`.
+///
+/// `ComputeBound` only ever sees the shape in its own local space, so a rotated shape
+/// reports a bound that no longer encloses it once the rotation is applied. This trait
+/// takes the transform into account, giving BVH/broadphase code a correct world-space
+/// bound for oriented shapes.
+pub trait ComputeBoundTransformed<S, B>
+where
+    S: BaseFloat,
+{
+    /// Compute the bound of `self` as placed in the world by `transform`.
+    fn compute_bound_transformed<T>(&self, transform: &T) -> B
+    where
+        T: Transform<Point3<S>>;
+}
@@ -0,0 +1,25 @@
+use cgmath::{BaseFloat, Matrix3, Point3};
+
+/// The rigid-body mass properties of a shape at a given density: how much it weighs,
+/// where its center of mass sits, and how its mass is distributed (its inertia tensor).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties<S> {
+    /// Volume of the shape.
+    pub volume: S,
+    /// Mass of the shape (`density * volume`).
+    pub mass: S,
+    /// Center of mass, in the shape's local space.
+    pub center_of_mass: Point3<S>,
+    /// Inertia tensor about the center of mass, in the shape's local space.
+    pub inertia_tensor: Matrix3<S>,
+}
+
+/// Compute the mass properties of a shape, so a rigid-body layer built on top of this
+/// crate's primitives doesn't have to hand-derive volume/inertia for each one.
+pub trait ComputeMassProperties<S>
+where
+    S: BaseFloat,
+{
+    /// Compute the mass properties of `self` at the given `density`.
+    fn compute_mass_properties(&self, density: S) -> MassProperties<S>;
+}
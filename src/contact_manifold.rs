@@ -0,0 +1,42 @@
+//! A manifold holds its points in a heap-allocated `Vec`, so this module needs `alloc` on
+//! `no_std` targets (see the crate's `std`/`alloc` feature wiring).
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use cgmath::{BaseFloat, Point3, Vector3};
+
+/// A single point in a [`ContactManifold`]: a world-space position together with how far
+/// the two shapes interpenetrate, measured along the manifold's normal at that point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactPoint<S> {
+    /// World-space position of the contact point.
+    pub position: Point3<S>,
+    /// How far the shapes overlap at this point, measured along the manifold's normal.
+    pub penetration: S,
+}
+
+/// A set of contact points sharing a single separating-axis normal.
+///
+/// Unlike a single GJK/EPA witness point, a manifold captures the whole contact area (a
+/// clipped face, or the closest-point pair between two near-parallel edges) so a
+/// constraint solver gets a stable set of points to resolve rather than one point that can
+/// jitter between frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactManifold<S> {
+    /// Contact normal, pointing away from `self` and into the other shape.
+    pub normal: Vector3<S>,
+    /// The contact points, each sharing `normal`.
+    pub points: Vec<ContactPoint<S>>,
+}
+
+impl<S> ContactManifold<S>
+where
+    S: BaseFloat,
+{
+    pub(crate) fn new(normal: Vector3<S>, points: Vec<ContactPoint<S>>) -> Self {
+        Self { normal, points }
+    }
+}
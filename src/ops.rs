@@ -0,0 +1,94 @@
+//! Internal float-operation dispatch, so primitive code builds the same whether it's
+//! linked against `std` or running `no_std` with the `libm` feature.
+//!
+//! Every transcendental or rounding operation the primitives need funnels through here.
+//! Without the `libm` feature this is a thin pass-through to `BaseFloat`'s own methods.
+//! With it, operations run through `libm` instead, so embedded/WASM targets without
+//! `std`'s (unspecified-precision) float intrinsics still get bit-exact results matching
+//! every other target.
+
+use cgmath::prelude::*;
+use cgmath::BaseFloat;
+
+/// Absolute value.
+#[inline]
+pub fn abs<S: BaseFloat>(x: S) -> S {
+    x.abs()
+}
+
+/// Sign of `x`: `1` or `-1` (see `BaseFloat::signum`).
+#[inline]
+pub fn signum<S: BaseFloat>(x: S) -> S {
+    x.signum()
+}
+
+/// Greater of `a` and `b`.
+#[inline]
+pub fn max<S: BaseFloat>(a: S, b: S) -> S {
+    a.max(b)
+}
+
+/// Square root.
+#[cfg(not(feature = "libm"))]
+#[inline]
+pub fn sqrt<S: BaseFloat>(x: S) -> S {
+    x.sqrt()
+}
+
+/// Square root, computed via `libm`.
+#[cfg(feature = "libm")]
+#[inline]
+pub fn sqrt<S: BaseFloat>(x: S) -> S {
+    use num_traits::NumCast;
+
+    let value: f64 = NumCast::from(x).expect("float value out of range for f64");
+    NumCast::from(libm::sqrt(value)).expect("libm::sqrt result out of range for S")
+}
+
+/// Normalize `v` to unit length, routing the reciprocal square root through [`sqrt`].
+#[inline]
+pub fn normalize<S, V>(v: V) -> V
+where
+    S: BaseFloat,
+    V: InnerSpace<Scalar = S>,
+{
+    v * (S::one() / sqrt(v.magnitude2()))
+}
+
+/// Four-quadrant arctangent of `y / x`.
+#[cfg(not(feature = "libm"))]
+#[inline]
+pub fn atan2<S: BaseFloat>(y: S, x: S) -> S {
+    y.atan2(x)
+}
+
+/// Four-quadrant arctangent of `y / x`, computed via `libm`.
+#[cfg(feature = "libm")]
+#[inline]
+pub fn atan2<S: BaseFloat>(y: S, x: S) -> S {
+    use num_traits::NumCast;
+
+    let y: f64 = NumCast::from(y).expect("float value out of range for f64");
+    let x: f64 = NumCast::from(x).expect("float value out of range for f64");
+    NumCast::from(libm::atan2(y, x)).expect("libm::atan2 result out of range for S")
+}
+
+/// Simultaneous sine and cosine of `x`, as `(sin, cos)`.
+#[cfg(not(feature = "libm"))]
+#[inline]
+pub fn sin_cos<S: BaseFloat>(x: S) -> (S, S) {
+    x.sin_cos()
+}
+
+/// Simultaneous sine and cosine of `x`, as `(sin, cos)`, computed via `libm`.
+#[cfg(feature = "libm")]
+#[inline]
+pub fn sin_cos<S: BaseFloat>(x: S) -> (S, S) {
+    use num_traits::NumCast;
+
+    let value: f64 = NumCast::from(x).expect("float value out of range for f64");
+    (
+        NumCast::from(libm::sin(value)).expect("libm::sin result out of range for S"),
+        NumCast::from(libm::cos(value)).expect("libm::cos result out of range for S"),
+    )
+}
@@ -0,0 +1,28 @@
+use cgmath::prelude::*;
+use cgmath::{BaseFloat, Plane, Point3};
+
+/// The classification of a bounding volume against a plane, as produced by
+/// [`PlaneRelation::relate_plane`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaneSide {
+    /// The volume lies entirely on the side the plane's normal points towards.
+    InFront,
+    /// The volume lies entirely on the side opposite the plane's normal.
+    Behind,
+    /// The plane cuts through the volume.
+    Crossing,
+}
+
+/// Classify a bounding volume against an oriented plane.
+///
+/// This is the core primitive for frustum culling: test a shape against each of the six
+/// camera planes and skip it as soon as one test returns [`PlaneSide::Behind`].
+pub trait PlaneRelation<S>
+where
+    S: BaseFloat,
+{
+    /// Classify `self`, placed in the world via `transform`, against `plane`.
+    fn relate_plane<T>(&self, plane: Plane<S>, transform: &T) -> PlaneSide
+    where
+        T: Transform<Point3<S>>;
+}
@@ -1,6 +1,10 @@
 use cgmath::prelude::*;
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Matrix3, Plane, Point3, Vector3};
 
+use crate::compute_bound_transformed::ComputeBoundTransformed;
+use crate::compute_mass_properties::{ComputeMassProperties, MassProperties};
+use crate::ops;
+use crate::plane_relation::{PlaneRelation, PlaneSide};
 use crate::prelude::*;
 use crate::primitive::util::get_max_point;
 use crate::volume::Sphere;
@@ -78,12 +82,13 @@ where
         &self,
         normal: &<Self::Point as EuclideanSpace>::Diff,
     ) -> <Self::Point as EuclideanSpace>::Diff {
-        if normal.x.abs() > normal.y.abs() && normal.x.abs() > normal.z.abs() {
-            Vector3::new(normal.x.signum(), Zero::zero(), Zero::zero())
-        } else if normal.y.abs() > normal.z.abs() && normal.y.abs() >= normal.x.abs() {
-            Vector3::new(Zero::zero(), normal.y.signum(), Zero::zero())
+        if ops::abs(normal.x) > ops::abs(normal.y) && ops::abs(normal.x) > ops::abs(normal.z) {
+            Vector3::new(ops::signum(normal.x), Zero::zero(), Zero::zero())
+        } else if ops::abs(normal.y) > ops::abs(normal.z) && ops::abs(normal.y) >= ops::abs(normal.x)
+        {
+            Vector3::new(Zero::zero(), ops::signum(normal.y), Zero::zero())
         } else {
-            Vector3::new(Zero::zero(), Zero::zero(), normal.z.signum())
+            Vector3::new(Zero::zero(), Zero::zero(), ops::signum(normal.z))
         }
     }
 }
@@ -105,7 +110,7 @@ where
     S: BaseFloat,
 {
     fn compute_bound(&self) -> Sphere<S> {
-        let max = self.half_dim.x.max(self.half_dim.y).max(self.half_dim.z);
+        let max = ops::max(ops::max(self.half_dim.x, self.half_dim.y), self.half_dim.z);
         Sphere {
             center: Point3::origin(),
             radius: max,
@@ -155,6 +160,174 @@ where
     }
 }
 
+impl<S> Cuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Compute the entry and exit points of a ray through this cuboid, using the slab
+    /// method, each paired with the surface normal at that point.
+    ///
+    /// Unlike [`ContinuousNormal::intersection_normal`], which only reports the near
+    /// hit, this returns both the near and far intersection so callers (CSG, translucency,
+    /// "inside the box" queries) can recover the full entry/exit segment. Returns `None`
+    /// if the ray misses the box, or the box lies entirely behind the ray origin. If the
+    /// ray originates inside the box, `t_near` is clamped to `0` so the near point is the
+    /// ray origin itself.
+    pub fn intersection_segment(
+        &self,
+        ray: &Ray3<S>,
+    ) -> Option<(Point3<S>, Vector3<S>, Point3<S>, Vector3<S>)> {
+        let mut t_near = -S::infinity();
+        let mut t_far = S::infinity();
+        let mut near_normal = Vector3::zero();
+        let mut far_normal = Vector3::zero();
+
+        for axis in 0..3 {
+            let (o, d, half, unit) = match axis {
+                0 => (ray.origin.x, ray.direction.x, self.half_dim.x, Vector3::unit_x()),
+                1 => (ray.origin.y, ray.direction.y, self.half_dim.y, Vector3::unit_y()),
+                _ => (ray.origin.z, ray.direction.z, self.half_dim.z, Vector3::unit_z()),
+            };
+
+            if d.is_zero() {
+                if o < -half || o > half {
+                    return None;
+                }
+                continue;
+            }
+
+            let mut t1 = (-half - o) / d;
+            let mut t2 = (half - o) / d;
+            let mut n1 = -unit;
+            let mut n2 = unit;
+            if t1 > t2 {
+                core::mem::swap(&mut t1, &mut t2);
+                core::mem::swap(&mut n1, &mut n2);
+            }
+
+            if t1 > t_near {
+                t_near = t1;
+                near_normal = n1;
+            }
+            if t2 < t_far {
+                t_far = t2;
+                far_normal = n2;
+            }
+        }
+
+        if t_near > t_far || t_far < S::zero() {
+            return None;
+        }
+
+        let t_near = t_near.max(S::zero());
+
+        Some((
+            ray.origin + ray.direction * t_near,
+            near_normal,
+            ray.origin + ray.direction * t_far,
+            far_normal,
+        ))
+    }
+}
+
+impl<S> ComputeBoundTransformed<S, Aabb3<S>> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound_transformed<T>(&self, transform: &T) -> Aabb3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let mut corners = self.corners.iter().map(|p| transform.transform_point(*p));
+        let first = corners.next().expect("cuboid always has corners");
+        let (min, max) = corners.fold((first, first), |(min, max), p| {
+            (
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        Aabb3::new(min, max)
+    }
+}
+
+/// The sphere is centered on the transformed origin and its radius is the largest
+/// half-dimension, the same radius as `ComputeBound<Sphere<S>>`. That radius reaches the
+/// center of every face but not the corners, so the sphere does not enclose the whole
+/// cuboid.
+impl<S> ComputeBoundTransformed<S, Sphere<S>> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound_transformed<T>(&self, transform: &T) -> Sphere<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let max = ops::max(ops::max(self.half_dim.x, self.half_dim.y), self.half_dim.z);
+        Sphere {
+            center: transform.transform_point(Point3::origin()),
+            radius: max,
+        }
+    }
+}
+
+impl<S> PlaneRelation<S> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    fn relate_plane<T>(&self, plane: Plane<S>, transform: &T) -> PlaneSide
+    where
+        T: Transform<Point3<S>>,
+    {
+        let center = transform.transform_point(Point3::origin());
+        let axis_x = transform.transform_vector(Vector3::unit_x());
+        let axis_y = transform.transform_vector(Vector3::unit_y());
+        let axis_z = transform.transform_vector(Vector3::unit_z());
+
+        let c = plane.n.dot(center.to_vec()) + plane.d;
+        let r = self.half_dim.x * ops::abs(plane.n.dot(axis_x))
+            + self.half_dim.y * ops::abs(plane.n.dot(axis_y))
+            + self.half_dim.z * ops::abs(plane.n.dot(axis_z));
+
+        if c - r > S::zero() {
+            PlaneSide::InFront
+        } else if c + r < S::zero() {
+            PlaneSide::Behind
+        } else {
+            PlaneSide::Crossing
+        }
+    }
+}
+
+impl<S> ComputeMassProperties<S> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_mass_properties(&self, density: S) -> MassProperties<S> {
+        let twelve = {
+            let two = S::one() + S::one();
+            two * two * (two + S::one())
+        };
+
+        let volume = self.dim.x * self.dim.y * self.dim.z;
+        let mass = density * volume;
+
+        let dx2 = self.dim.x * self.dim.x;
+        let dy2 = self.dim.y * self.dim.y;
+        let dz2 = self.dim.z * self.dim.z;
+
+        MassProperties {
+            volume,
+            mass,
+            center_of_mass: Point3::origin(),
+            inertia_tensor: Matrix3::from_diagonal(Vector3::new(
+                mass * (dy2 + dz2) / twelve,
+                mass * (dx2 + dz2) / twelve,
+                mass * (dx2 + dy2) / twelve,
+            )),
+        }
+    }
+}
+
 /// Cuboid primitive.
 ///
 /// Have a cached set of corner points to speed up computation.
@@ -184,6 +357,16 @@ where
     pub fn half_dim(&self) -> S {
         self.cuboid.half_dim.x
     }
+
+    /// Compute the entry and exit points of a ray through this cube.
+    ///
+    /// See [`Cuboid::intersection_segment`] for details.
+    pub fn intersection_segment(
+        &self,
+        ray: &Ray3<S>,
+    ) -> Option<(Point3<S>, Vector3<S>, Point3<S>, Vector3<S>)> {
+        self.cuboid.intersection_segment(ray)
+    }
 }
 
 impl<S> Primitive for Cube<S>
@@ -256,11 +439,56 @@ where
     }
 }
 
+impl<S> PlaneRelation<S> for Cube<S>
+where
+    S: BaseFloat,
+{
+    fn relate_plane<T>(&self, plane: Plane<S>, transform: &T) -> PlaneSide
+    where
+        T: Transform<Point3<S>>,
+    {
+        self.cuboid.relate_plane(plane, transform)
+    }
+}
+
+impl<S> ComputeBoundTransformed<S, Aabb3<S>> for Cube<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound_transformed<T>(&self, transform: &T) -> Aabb3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        self.cuboid.compute_bound_transformed(transform)
+    }
+}
+
+impl<S> ComputeBoundTransformed<S, Sphere<S>> for Cube<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound_transformed<T>(&self, transform: &T) -> Sphere<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        self.cuboid.compute_bound_transformed(transform)
+    }
+}
+
+impl<S> ComputeMassProperties<S> for Cube<S>
+where
+    S: BaseFloat,
+{
+    fn compute_mass_properties(&self, density: S) -> MassProperties<S> {
+        self.cuboid.compute_mass_properties(density)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use approx::assert_ulps_eq;
-    use cgmath::{vec3, Decomposed, Point3, Quaternion, Rad, Vector3};
+    use cgmath::{vec3, Decomposed, Plane, Point3, Quaternion, Rad, Vector3};
 
     use super::*;
     use Ray3;
@@ -410,6 +638,101 @@ mod tests {
         assert_ulps_eq!(0., p.1.z);
     }
 
+    #[test]
+    fn test_intersection_segment() {
+        let cuboid = Cuboid::new(10., 10., 10.);
+        let ray = Ray3::new(Point3::new(-10., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(
+            Some((
+                Point3::new(-5., 0., 0.),
+                vec3(-1., 0., 0.),
+                Point3::new(5., 0., 0.),
+                vec3(1., 0., 0.),
+            )),
+            cuboid.intersection_segment(&ray)
+        );
+    }
+
+    #[test]
+    fn test_intersection_segment_originating_inside() {
+        let cuboid = Cuboid::new(10., 10., 10.);
+        let ray = Ray3::new(Point3::new(0., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(
+            Some((
+                Point3::new(0., 0., 0.),
+                vec3(-1., 0., 0.),
+                Point3::new(5., 0., 0.),
+                vec3(1., 0., 0.),
+            )),
+            cuboid.intersection_segment(&ray)
+        );
+    }
+
+    #[test]
+    fn test_intersection_segment_miss() {
+        let cuboid = Cuboid::new(10., 10., 10.);
+        let ray = Ray3::new(Point3::new(10., 10., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(None, cuboid.intersection_segment(&ray));
+    }
+
+    #[test]
+    fn test_intersection_segment_behind() {
+        let cuboid = Cuboid::new(10., 10., 10.);
+        let ray = Ray3::new(Point3::new(10., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(None, cuboid.intersection_segment(&ray));
+    }
+
+    #[test]
+    fn test_relate_plane() {
+        let cuboid = Cuboid::new(2., 2., 2.);
+        let identity = transform(0., 0., 0., 0.);
+        let plane = Plane::new(vec3(1., 0., 0.), 5.);
+
+        assert_eq!(
+            PlaneSide::InFront,
+            cuboid.relate_plane(plane, &identity)
+        );
+        assert_eq!(
+            PlaneSide::Behind,
+            cuboid.relate_plane(Plane::new(vec3(1., 0., 0.), -5.), &identity)
+        );
+        assert_eq!(
+            PlaneSide::Crossing,
+            cuboid.relate_plane(Plane::new(vec3(1., 0., 0.), 0.), &identity)
+        );
+    }
+
+    #[test]
+    fn test_compute_mass_properties() {
+        let cuboid = Cuboid::new(2., 4., 6.);
+        let props = cuboid.compute_mass_properties(2.);
+
+        assert_ulps_eq!(48., props.volume);
+        assert_ulps_eq!(96., props.mass);
+        assert_ulps_eq!(Point3::new(0., 0., 0.), props.center_of_mass);
+
+        let expected_xx = props.mass * (4f32 * 4. + 6. * 6.) / 12.;
+        let expected_yy = props.mass * (2f32 * 2. + 6. * 6.) / 12.;
+        let expected_zz = props.mass * (2f32 * 2. + 4. * 4.) / 12.;
+        assert_ulps_eq!(expected_xx, props.inertia_tensor.x.x);
+        assert_ulps_eq!(expected_yy, props.inertia_tensor.y.y);
+        assert_ulps_eq!(expected_zz, props.inertia_tensor.z.z);
+    }
+
+    #[test]
+    fn test_compute_bound_transformed_rotated() {
+        let cuboid = Cuboid::new(2., 2., 2.);
+        let t = transform(0., 0., 0., ::std::f32::consts::FRAC_PI_4);
+        let bound: Aabb3<f32> = cuboid.compute_bound_transformed(&t);
+        let expected = 2f32.sqrt();
+        assert_ulps_eq!(-expected, bound.min.x);
+        assert_ulps_eq!(-expected, bound.min.y);
+        assert_ulps_eq!(-1., bound.min.z);
+        assert_ulps_eq!(expected, bound.max.x);
+        assert_ulps_eq!(expected, bound.max.y);
+        assert_ulps_eq!(1., bound.max.z);
+    }
+
     // util
     fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
         Decomposed {
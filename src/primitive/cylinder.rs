@@ -1,11 +1,117 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
 use cgmath::prelude::*;
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Matrix3, Plane, Point3, Rad, Vector3};
 
+use crate::compute_mass_properties::{ComputeMassProperties, MassProperties};
+use crate::contact_manifold::{ContactManifold, ContactPoint};
+use crate::ops;
 use crate::prelude::*;
 use crate::primitive::util::cylinder_ray_quadratic_solve;
 use crate::volume::Sphere;
 use crate::{Aabb3, Ray3};
 
+/// A surface feature of a `Cylinder`, identifying which boundary an
+/// `intersection_interval` endpoint landed on so the right normal can be computed for it.
+#[derive(Debug, Clone, Copy)]
+enum CylinderFeature {
+    Side,
+    TopCap,
+    BottomCap,
+}
+
+fn cylinder_feature_normal<S: BaseFloat>(feature: CylinderFeature, p: Point3<S>) -> Vector3<S> {
+    match feature {
+        CylinderFeature::TopCap => Vector3::unit_y(),
+        CylinderFeature::BottomCap => -Vector3::unit_y(),
+        CylinderFeature::Side => ops::normalize(cgmath::vec3(p.x, S::zero(), p.z)),
+    }
+}
+
+/// Which candidate separating axis a [`Cylinder::contact_manifold_vs_cylinder`] result was
+/// generated from, so the point-generation step knows which feature is in contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CylinderAxisSource {
+    SelfAxis,
+    OtherAxis,
+    Cross,
+}
+
+/// Half-extent of a cylinder (axis `axis`, the given `half_height`/`radius`) when
+/// projected onto unit direction `d`.
+fn cylinder_axis_extent<S: BaseFloat>(half_height: S, radius: S, axis: Vector3<S>, d: Vector3<S>) -> S {
+    let cos_theta = axis.dot(d);
+    let sin_theta_sq = S::one() - cos_theta * cos_theta;
+    half_height * ops::abs(cos_theta) + radius * ops::sqrt(ops::max(S::zero(), sin_theta_sq))
+}
+
+/// Two unit vectors `(u, v)` spanning the plane perpendicular to unit vector `axis`, so
+/// `{axis, u, v}` is an orthonormal basis. Used to sample points around a cylinder cap.
+fn orthonormal_basis<S: BaseFloat>(axis: Vector3<S>) -> (Vector3<S>, Vector3<S>) {
+    let reference = if ops::abs(axis.dot(Vector3::unit_x())) < S::from(0.9).unwrap() {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    let u = ops::normalize(axis.cross(reference));
+    let v = axis.cross(u);
+    (u, v)
+}
+
+/// Closest points between line segments `p1..q1` and `p2..q2`, as
+/// `(point_on_first, point_on_second)`.
+fn closest_points_between_segments<S: BaseFloat>(
+    p1: Point3<S>,
+    q1: Point3<S>,
+    p2: Point3<S>,
+    q2: Point3<S>,
+) -> (Point3<S>, Point3<S>) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+
+    let (s, t) = if a.is_zero() && e.is_zero() {
+        (S::zero(), S::zero())
+    } else if a.is_zero() {
+        (S::zero(), (f / e).max(S::zero()).min(S::one()))
+    } else {
+        let c = d1.dot(r);
+        if e.is_zero() {
+            (ops::max(S::zero(), (-c / a).min(S::one())), S::zero())
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+
+            let mut s = if !denom.is_zero() {
+                ops::max(S::zero(), ((b * f - c * e) / denom).min(S::one()))
+            } else {
+                S::zero()
+            };
+
+            let mut t = (b * s + f) / e;
+
+            if t < S::zero() {
+                t = S::zero();
+                s = ops::max(S::zero(), (-c / a).min(S::one()));
+            } else if t > S::one() {
+                t = S::one();
+                s = ops::max(S::zero(), ((b - c) / a).min(S::one()));
+            }
+
+            (s, t)
+        }
+    };
+
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
 /// Cylinder primitive
 /// Cylinder body is aligned with the Y axis, with local origin in the center of the cylinders.
 #[derive(Debug, Clone, PartialEq)]
@@ -57,7 +163,7 @@ where
         if result.magnitude2().is_zero() {
             result = Zero::zero();
         } else {
-            result = result.normalize();
+            result = ops::normalize(result);
             if result.is_zero() {
                 result = Zero::zero(); // cancel out any inconsistencies
             } else {
@@ -81,7 +187,7 @@ where
         if normal.y.abs() > flat.magnitude() {
             Vector3::new(Zero::zero(), normal.y.signum(), Zero::zero())
         } else {
-            flat.normalize()
+            ops::normalize(flat)
         }
     }
 }
@@ -105,7 +211,7 @@ where
     fn compute_bound(&self) -> Sphere<S> {
         Sphere {
             center: Point3::origin(),
-            radius: ((self.radius * self.radius) + (self.half_height * self.half_height)).sqrt(),
+            radius: ops::sqrt((self.radius * self.radius) + (self.half_height * self.half_height)),
         }
     }
 }
@@ -275,7 +381,7 @@ where
         if hit_cap {
             pc.y = cap_y;
         } else {
-            normal = vec3(pc.x, S::zero(), pc.z).normalize();
+            normal = ops::normalize(vec3(pc.x, S::zero(), pc.z));
         }
 
         if (pc.y > self.half_height) || (pc.y < -self.half_height) {
@@ -286,12 +392,310 @@ where
     }
 }
 
+impl<S> Cylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Compute the entry and exit points of a ray through this solid cylinder, each
+    /// paired with its surface normal.
+    ///
+    /// Unlike [`ContinuousNormal::intersection_normal`], which only reports the near
+    /// hit, this returns both the near and far intersection so CSG, volume rendering,
+    /// and "ray starts inside" queries don't have to reconstruct the interval from the
+    /// single-hit API. Returns `None` if the ray misses the cylinder entirely. `t_near`
+    /// may be negative if the ray originates inside the cylinder.
+    pub fn intersection_interval(
+        &self,
+        r: &Ray3<S>,
+    ) -> Option<(S, Point3<S>, Vector3<S>, S, Point3<S>, Vector3<S>)> {
+        use cgmath::vec2;
+        use CylinderFeature::*;
+
+        // The interval cut out of the ray by the two cap planes.
+        let (cap_near_t, cap_near_feature, cap_far_t, cap_far_feature) =
+            if r.direction.y.is_zero() {
+                if r.origin.y > self.half_height || r.origin.y < -self.half_height {
+                    return None;
+                }
+                (-S::infinity(), Side, S::infinity(), Side)
+            } else {
+                let t_top = (self.half_height - r.origin.y) / r.direction.y;
+                let t_bottom = (-self.half_height - r.origin.y) / r.direction.y;
+                if t_bottom < t_top {
+                    (t_bottom, BottomCap, t_top, TopCap)
+                } else {
+                    (t_top, TopCap, t_bottom, BottomCap)
+                }
+            };
+
+        // The interval cut out of the ray by the infinite cylindrical side.
+        let (side_near_t, side_far_t) = if r.direction.x.is_zero() && r.direction.z.is_zero() {
+            // Ray parallel to the axis: no side roots, only radial containment matters.
+            if ops::sqrt(vec2(r.origin.x, r.origin.z).magnitude2()) > self.radius {
+                return None;
+            }
+            (-S::infinity(), S::infinity())
+        } else {
+            let (ta, tb) = cylinder_ray_quadratic_solve(r, self.radius)?;
+            if ta < tb {
+                (ta, tb)
+            } else {
+                (tb, ta)
+            }
+        };
+
+        let (t_near, near_feature) = if side_near_t > cap_near_t {
+            (side_near_t, Side)
+        } else {
+            (cap_near_t, cap_near_feature)
+        };
+        let (t_far, far_feature) = if side_far_t < cap_far_t {
+            (side_far_t, Side)
+        } else {
+            (cap_far_t, cap_far_feature)
+        };
+
+        if t_near > t_far || t_far < S::zero() {
+            return None;
+        }
+
+        let p_near = r.origin + r.direction * t_near;
+        let p_far = r.origin + r.direction * t_far;
+
+        Some((
+            t_near,
+            p_near,
+            cylinder_feature_normal(near_feature, p_near),
+            t_far,
+            p_far,
+            cylinder_feature_normal(far_feature, p_far),
+        ))
+    }
+}
+
+impl<S> ComputeMassProperties<S> for Cylinder<S>
+where
+    S: BaseFloat,
+{
+    fn compute_mass_properties(&self, density: S) -> MassProperties<S> {
+        let two = S::one() + S::one();
+        let pi = Rad::full_turn().0 / two;
+
+        let volume = pi * self.radius * self.radius * self.height();
+        let mass = density * volume;
+
+        let r2 = self.radius * self.radius;
+        let h2 = self.height() * self.height();
+        let three = two + S::one();
+        let twelve = three * two * two;
+        let i_xz = mass * (three * r2 + h2) / twelve;
+        let i_y = mass * r2 / two;
+
+        MassProperties {
+            volume,
+            mass,
+            center_of_mass: Point3::origin(),
+            inertia_tensor: Matrix3::from_diagonal(Vector3::new(i_xz, i_y, i_xz)),
+        }
+    }
+}
+
+impl<S> Cylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Generate the contact manifold between this cylinder and a half-space, analytically
+    /// rather than through GJK/EPA support-point iteration, which produces shallow, jittery
+    /// normals for a shape with flat caps and a curved side.
+    ///
+    /// `plane` is given in the same (world) space as `transform`'s target; the half-space
+    /// is the side `plane.n` points away from. Returns `None` if the shapes don't overlap.
+    /// Callers should fall back to the crate's general support-point GJK/EPA path when this
+    /// returns `None` but a coarser check (e.g. AABB overlap) says the shapes are touching.
+    pub fn contact_manifold_vs_plane<T>(
+        &self,
+        transform: &T,
+        plane: Plane<S>,
+    ) -> Option<ContactManifold<S>>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let center = transform.transform_point(Point3::origin());
+        let axis = ops::normalize(transform.transform_vector(Vector3::unit_y()));
+
+        let cos_theta = axis.dot(plane.n);
+        let extent = cylinder_axis_extent(self.half_height, self.radius, axis, plane.n);
+        let center_dist = plane.n.dot(center.to_vec()) + plane.d;
+        if extent - center_dist <= S::zero() {
+            return None;
+        }
+
+        let penetration_at = |p: Point3<S>| -(plane.n.dot(p.to_vec()) + plane.d);
+
+        // cos(45 deg): past this the nearer cap is closer to parallel with the plane than
+        // the side is, so treat the cap as the contact feature instead of the side.
+        let flush_threshold = ops::sqrt(S::one() + S::one()) / (S::one() + S::one());
+
+        let positions = if ops::abs(cos_theta) >= flush_threshold {
+            let cap_sign = if cos_theta > S::zero() {
+                -S::one()
+            } else {
+                S::one()
+            };
+            let cap_center = center + axis * (cap_sign * self.half_height);
+            let (u, v) = orthonormal_basis(axis);
+
+            const SEGMENTS: usize = 8;
+            (0..SEGMENTS)
+                .map(|i| {
+                    let angle =
+                        Rad::full_turn().0 * S::from(i).unwrap() / S::from(SEGMENTS).unwrap();
+                    let (sin, cos) = ops::sin_cos(angle);
+                    cap_center + (u * cos + v * sin) * self.radius
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let n_perp = plane.n - axis * axis.dot(plane.n);
+            let radial_dir = if !n_perp.magnitude2().is_zero() {
+                -ops::normalize(n_perp)
+            } else {
+                orthonormal_basis(axis).0
+            };
+            vec![
+                center + axis * self.half_height + radial_dir * self.radius,
+                center - axis * self.half_height + radial_dir * self.radius,
+            ]
+        };
+
+        let points: Vec<_> = positions
+            .into_iter()
+            .map(|position| ContactPoint {
+                position,
+                penetration: penetration_at(position),
+            })
+            .filter(|p| p.penetration > S::zero())
+            .collect();
+
+        if points.is_empty() {
+            return None;
+        }
+
+        Some(ContactManifold::new(-plane.n, points))
+    }
+
+    /// Generate the contact manifold between this cylinder and `other`, analytically
+    /// rather than through GJK/EPA support-point iteration.
+    ///
+    /// Candidate separating axes are this cylinder's own axis, `other`'s axis, and the
+    /// (normalized) cross product of the two. Returns `None` as soon as one of them
+    /// separates the shapes. If the minimum-penetration axis is a cylinder axis, the
+    /// contact is treated as that cylinder's near cap against the other body, approximated
+    /// by the cap's rim (accurate when the two axes are close to parallel, as with stacked
+    /// pucks or pipe segments). If it's the cross-product axis, the contact is the
+    /// closest-point pair between the two axis segments offset outward by each cylinder's
+    /// radius — the case that makes support-point EPA degenerate for near-parallel sides.
+    pub fn contact_manifold_vs_cylinder<T>(
+        &self,
+        transform: &T,
+        other: &Cylinder<S>,
+        other_transform: &T,
+    ) -> Option<ContactManifold<S>>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let center1 = transform.transform_point(Point3::origin());
+        let center2 = other_transform.transform_point(Point3::origin());
+        let axis1 = ops::normalize(transform.transform_vector(Vector3::unit_y()));
+        let axis2 = ops::normalize(other_transform.transform_vector(Vector3::unit_y()));
+        let d = center2 - center1;
+
+        let cross = axis1.cross(axis2);
+        let mut candidates = vec![
+            (axis1, CylinderAxisSource::SelfAxis),
+            (axis2, CylinderAxisSource::OtherAxis),
+        ];
+        if !cross.magnitude2().is_zero() {
+            candidates.push((ops::normalize(cross), CylinderAxisSource::Cross));
+        }
+
+        let mut best: Option<(Vector3<S>, CylinderAxisSource, S)> = None;
+        for (axis, source) in candidates {
+            let extent1 = cylinder_axis_extent(self.half_height, self.radius, axis1, axis);
+            let extent2 = cylinder_axis_extent(other.half_height, other.radius, axis2, axis);
+            let separation = d.dot(axis);
+            let overlap = extent1 + extent2 - ops::abs(separation);
+
+            if overlap <= S::zero() {
+                return None;
+            }
+
+            let oriented = if separation < S::zero() { -axis } else { axis };
+            if best.map_or(true, |(_, _, best_overlap)| overlap < best_overlap) {
+                best = Some((oriented, source, overlap));
+            }
+        }
+        let (normal, source, penetration) =
+            best.expect("self's axis is always a tested candidate");
+
+        let positions = match source {
+            CylinderAxisSource::Cross => {
+                let p1 = center1 - axis1 * self.half_height;
+                let q1 = center1 + axis1 * self.half_height;
+                let p2 = center2 - axis2 * other.half_height;
+                let q2 = center2 + axis2 * other.half_height;
+                let (c1, c2) = closest_points_between_segments(p1, q1, p2, q2);
+                vec![c1 + normal * self.radius, c2 - normal * other.radius]
+            }
+            CylinderAxisSource::SelfAxis | CylinderAxisSource::OtherAxis => {
+                let (owner_center, owner_axis, owner_half_height, owner_radius) =
+                    if source == CylinderAxisSource::SelfAxis {
+                        (center1, axis1, self.half_height, self.radius)
+                    } else {
+                        (center2, axis2, other.half_height, other.radius)
+                    };
+                // `normal` points from self to other; the contacting cap is the one
+                // facing that direction for self, or facing back towards self for other.
+                let facing = if source == CylinderAxisSource::SelfAxis {
+                    normal
+                } else {
+                    -normal
+                };
+                let cap_sign = if facing.dot(owner_axis) > S::zero() {
+                    S::one()
+                } else {
+                    -S::one()
+                };
+                let cap_center = owner_center + owner_axis * (cap_sign * owner_half_height);
+                let (u, v) = orthonormal_basis(owner_axis);
+
+                const SEGMENTS: usize = 8;
+                (0..SEGMENTS)
+                    .map(|i| {
+                        let angle = Rad::full_turn().0 * S::from(i).unwrap()
+                            / S::from(SEGMENTS).unwrap();
+                        let (sin, cos) = ops::sin_cos(angle);
+                        cap_center + (u * cos + v * sin) * owner_radius
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        Some(ContactManifold::new(
+            normal,
+            positions
+                .into_iter()
+                .map(|position| ContactPoint { position, penetration })
+                .collect(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std;
 
     use approx::assert_ulps_eq;
-    use cgmath::{vec3, Decomposed, Quaternion, Rad, Vector3};
+    use cgmath::{vec3, Decomposed, Plane, Quaternion, Rad, Vector3};
 
     use super::*;
 
@@ -545,6 +949,126 @@ mod tests {
         assert_eq!(None, cylinder.intersection(&ray));
     }
 
+    #[test]
+    fn test_intersection_interval_through_side() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+        let (t_near, p_near, n_near, t_far, p_far, n_far) =
+            cylinder.intersection_interval(&ray).unwrap();
+        assert_ulps_eq!(2., t_near);
+        assert_ulps_eq!(Point3::new(-1., 0., 0.), p_near);
+        assert_ulps_eq!(vec3(-1., 0., 0.), n_near);
+        assert_ulps_eq!(4., t_far);
+        assert_ulps_eq!(Point3::new(1., 0., 0.), p_far);
+        assert_ulps_eq!(vec3(1., 0., 0.), n_far);
+    }
+
+    #[test]
+    fn test_intersection_interval_through_caps() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(0., 3., 0.), Vector3::new(0., -1., 0.));
+        let (t_near, p_near, n_near, t_far, p_far, n_far) =
+            cylinder.intersection_interval(&ray).unwrap();
+        assert_ulps_eq!(1., t_near);
+        assert_ulps_eq!(Point3::new(0., 2., 0.), p_near);
+        assert_ulps_eq!(vec3(0., 1., 0.), n_near);
+        assert_ulps_eq!(5., t_far);
+        assert_ulps_eq!(Point3::new(0., -2., 0.), p_far);
+        assert_ulps_eq!(vec3(0., -1., 0.), n_far);
+    }
+
+    #[test]
+    fn test_intersection_interval_origin_inside() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(0., 0., 0.), Vector3::new(1., 0., 0.));
+        let (t_near, p_near, n_near, t_far, p_far, n_far) =
+            cylinder.intersection_interval(&ray).unwrap();
+        assert_ulps_eq!(-1., t_near);
+        assert_ulps_eq!(Point3::new(-1., 0., 0.), p_near);
+        assert_ulps_eq!(vec3(-1., 0., 0.), n_near);
+        assert_ulps_eq!(1., t_far);
+        assert_ulps_eq!(Point3::new(1., 0., 0.), p_far);
+        assert_ulps_eq!(vec3(1., 0., 0.), n_far);
+    }
+
+    #[test]
+    fn test_intersection_interval_miss() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert_eq!(None, cylinder.intersection_interval(&ray));
+    }
+
+    #[test]
+    fn test_compute_mass_properties() {
+        let cylinder = Cylinder::new(2., 1.);
+        let props = cylinder.compute_mass_properties(2.);
+
+        let expected_volume = std::f64::consts::PI * 1. * 1. * 4.;
+        assert_ulps_eq!(expected_volume, props.volume);
+        assert_ulps_eq!(expected_volume * 2., props.mass);
+        assert_ulps_eq!(Point3::new(0., 0., 0.), props.center_of_mass);
+
+        let expected_i_xz = props.mass * (3. * 1. + 4. * 4.) / 12.;
+        let expected_i_y = props.mass * 1. / 2.;
+        assert_ulps_eq!(expected_i_xz, props.inertia_tensor.x.x);
+        assert_ulps_eq!(expected_i_y, props.inertia_tensor.y.y);
+        assert_ulps_eq!(expected_i_xz, props.inertia_tensor.z.z);
+        assert_ulps_eq!(0., props.inertia_tensor.x.y);
+        assert_ulps_eq!(0., props.inertia_tensor.x.z);
+        assert_ulps_eq!(0., props.inertia_tensor.y.z);
+    }
+
+    #[test]
+    fn test_contact_manifold_vs_plane_cap() {
+        let cylinder = Cylinder::new(2., 1.);
+        let t = transform(0., 0., 0., 0.);
+        let plane = Plane::new(vec3(0., 1., 0.), 1.5);
+
+        let manifold = cylinder.contact_manifold_vs_plane(&t, plane).unwrap();
+
+        assert_ulps_eq!(vec3(0., -1., 0.), manifold.normal);
+        assert_eq!(8, manifold.points.len());
+        for point in &manifold.points {
+            assert_ulps_eq!(-2., point.position.y);
+            let r = point.position.x * point.position.x + point.position.z * point.position.z;
+            assert_ulps_eq!(1., r);
+            assert_ulps_eq!(0.5, point.penetration);
+        }
+    }
+
+    #[test]
+    fn test_contact_manifold_vs_plane_side() {
+        let cylinder = Cylinder::new(2., 1.);
+        let t = transform(0., 0., 0., ::std::f32::consts::FRAC_PI_2);
+        let plane = Plane::new(vec3(0., 1., 0.), 0.7);
+
+        let manifold = cylinder.contact_manifold_vs_plane(&t, plane).unwrap();
+
+        assert_ulps_eq!(vec3(0., -1., 0.), manifold.normal);
+        assert_eq!(2, manifold.points.len());
+        for point in &manifold.points {
+            assert_ulps_eq!(-1., point.position.y);
+            assert_ulps_eq!(0.3, point.penetration);
+        }
+    }
+
+    #[test]
+    fn test_contact_manifold_vs_cylinder_cross_axis() {
+        let a = Cylinder::new(2., 1.);
+        let b = Cylinder::new(2., 1.);
+        let ta = transform(0., 0., 0., 0.);
+        let tb = transform(0., 0., 1.5, ::std::f32::consts::FRAC_PI_2);
+
+        let manifold = a.contact_manifold_vs_cylinder(&ta, &b, &tb).unwrap();
+
+        assert_ulps_eq!(vec3(0., 0., 1.), manifold.normal);
+        assert_eq!(2, manifold.points.len());
+        assert_ulps_eq!(Point3::new(0., 0., 1.), manifold.points[0].position);
+        assert_ulps_eq!(0.5, manifold.points[0].penetration);
+        assert_ulps_eq!(Point3::new(0., 0., 0.5), manifold.points[1].position);
+        assert_ulps_eq!(0.5, manifold.points[1].penetration);
+    }
+
     // util
     fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
         Decomposed {
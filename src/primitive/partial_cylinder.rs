@@ -0,0 +1,328 @@
+use cgmath::prelude::*;
+use cgmath::{BaseFloat, Point3, Rad, Vector3};
+
+use crate::ops;
+use crate::prelude::*;
+use crate::primitive::util::cylinder_ray_quadratic_solve;
+use crate::volume::Sphere;
+use crate::{Aabb3, Ray3};
+
+fn wrap_angle<S: BaseFloat>(angle: S) -> S {
+    let two_pi = Rad::full_turn().0;
+    let mut a = angle % two_pi;
+    if a < S::zero() {
+        a = a + two_pi;
+    }
+    a
+}
+
+/// Smallest angle between two directions, in `[0, pi]`.
+fn angular_distance<S: BaseFloat>(a: S, b: S) -> S {
+    let two_pi = Rad::full_turn().0;
+    let d = wrap_angle(a - b);
+    if d > two_pi / (S::one() + S::one()) {
+        two_pi - d
+    } else {
+        d
+    }
+}
+
+/// A sectored (partial) cylinder primitive.
+///
+/// Like [`Cylinder`](crate::primitive::Cylinder), the body is aligned with the Y axis
+/// with local origin at its center, but the side and caps are cut down to the angular
+/// range `phi_min..phi_max` (measured from the positive X axis, around Y), letting this
+/// model pipe segments, tube walls, and wedges. A full sweep (`phi_max - phi_min >= 2*pi`)
+/// degrades exactly to a full `Cylinder`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartialCylinder<S> {
+    half_height: S,
+    radius: S,
+    phi_min: S,
+    phi_max: S,
+}
+
+impl<S> PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new partial cylinder, sweeping from `phi_min` to `phi_max` around the Y
+    /// axis (angle `0` is the positive X axis).
+    pub fn new(half_height: S, radius: S, phi_min: Rad<S>, phi_max: Rad<S>) -> Self {
+        let two_pi = Rad::full_turn().0;
+        let phi_min = wrap_angle(phi_min.0);
+        let mut sweep = (phi_max.0 - phi_min) % two_pi;
+        if sweep <= S::zero() {
+            sweep = sweep + two_pi;
+        }
+        Self {
+            half_height,
+            radius,
+            phi_min,
+            phi_max: phi_min + sweep,
+        }
+    }
+
+    /// Get radius
+    pub fn radius(&self) -> S {
+        self.radius
+    }
+
+    /// Get height
+    pub fn height(&self) -> S {
+        self.half_height + self.half_height
+    }
+
+    /// Get the angular sweep of the cylinder, `phi_min..phi_max`.
+    pub fn phi_range(&self) -> (Rad<S>, Rad<S>) {
+        (Rad(self.phi_min), Rad(self.phi_max))
+    }
+
+    fn is_full(&self) -> bool {
+        self.phi_max - self.phi_min >= Rad::full_turn().0
+    }
+
+    fn contains_phi(&self, phi: S) -> bool {
+        if self.is_full() {
+            return true;
+        }
+        let p = self.phi_min + wrap_angle(phi - self.phi_min);
+        p <= self.phi_max
+    }
+
+    /// Clamp `phi` to the nearest point on the arc `[phi_min, phi_max]`.
+    fn clamp_phi(&self, phi: S) -> S {
+        if self.is_full() || self.contains_phi(phi) {
+            return phi;
+        }
+        if angular_distance(phi, self.phi_min) <= angular_distance(phi, self.phi_max) {
+            self.phi_min
+        } else {
+            self.phi_max
+        }
+    }
+
+    /// The parametric `(u, v)` coordinates of a point `p` on the surface of this
+    /// cylinder, with `u` sweeping the arc and `v` sweeping the height.
+    pub fn surface_uv(&self, p: Point3<S>) -> (S, S) {
+        let phi = self.phi_min + wrap_angle(ops::atan2(p.z, p.x) - self.phi_min);
+        let u = (phi - self.phi_min) / (self.phi_max - self.phi_min);
+        let v = (p.y + self.half_height) / self.height();
+        (u, v)
+    }
+
+    /// The ray parameter `t` of the nearest hit on the (possibly clipped) surface in the
+    /// direction of `ray`, together with the outward surface normal there.
+    fn hit(&self, r: &Ray3<S>) -> Option<(S, Vector3<S>)> {
+        let mut best: Option<(S, Vector3<S>)> = None;
+        let mut consider = |t: S, normal: Vector3<S>| {
+            if t < S::zero() {
+                return;
+            }
+            if best.map_or(true, |(best_t, _)| t < best_t) {
+                best = Some((t, normal));
+            }
+        };
+
+        if !(r.direction.x.is_zero() && r.direction.z.is_zero()) {
+            if let Some((t1, t2)) = cylinder_ray_quadratic_solve(r, self.radius) {
+                for &t in &[t1, t2] {
+                    if t < S::zero() {
+                        continue;
+                    }
+                    let p = r.origin + r.direction * t;
+                    if p.y < -self.half_height || p.y > self.half_height {
+                        continue;
+                    }
+                    if !self.contains_phi(ops::atan2(p.z, p.x)) {
+                        continue;
+                    }
+                    consider(t, ops::normalize(Vector3::new(p.x, S::zero(), p.z)));
+                }
+            }
+        }
+
+        if !r.direction.y.is_zero() {
+            let t_top = (self.half_height - r.origin.y) / r.direction.y;
+            let t_bottom = (-self.half_height - r.origin.y) / r.direction.y;
+            for &(t, normal) in &[(t_top, Vector3::unit_y()), (t_bottom, -Vector3::unit_y())] {
+                if t < S::zero() {
+                    continue;
+                }
+                let p = r.origin + r.direction * t;
+                if p.x * p.x + p.z * p.z > self.radius * self.radius {
+                    continue;
+                }
+                if !self.contains_phi(ops::atan2(p.z, p.x)) {
+                    continue;
+                }
+                consider(t, normal);
+            }
+        }
+
+        best
+    }
+}
+
+impl<S> Primitive for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let negative = direction.y.is_sign_negative();
+
+        let flat = Vector3::new(direction.x, S::zero(), direction.z);
+        let mut result = if flat.magnitude2().is_zero() {
+            Zero::zero()
+        } else {
+            let phi = self.clamp_phi(ops::atan2(direction.z, direction.x));
+            let (sin, cos) = ops::sin_cos(phi);
+            Vector3::new(cos, S::zero(), sin) * self.radius
+        };
+
+        if negative {
+            result.y = -self.half_height;
+        } else {
+            result.y = self.half_height;
+        }
+        transform.transform_point(Point3::from_vec(result))
+    }
+
+    fn closest_valid_normal_local(
+        &self,
+        normal: &<Self::Point as EuclideanSpace>::Diff,
+    ) -> <Self::Point as EuclideanSpace>::Diff {
+        let flat = <Self::Point as EuclideanSpace>::Diff::new(normal.x, Zero::zero(), normal.z);
+
+        if normal.y.abs() > ops::sqrt(flat.magnitude2()) {
+            Vector3::new(Zero::zero(), normal.y.signum(), Zero::zero())
+        } else {
+            ops::normalize(flat)
+        }
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::new(-self.radius, -self.half_height, -self.radius),
+            Point3::new(self.radius, self.half_height, self.radius),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: ops::sqrt((self.radius * self.radius) + (self.half_height * self.half_height)),
+        }
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray3<S>) -> bool {
+        self.hit(r).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, r: &Ray3<S>) -> Option<Point3<S>> {
+        self.hit(r).map(|(t, _)| r.origin + r.direction * t)
+    }
+}
+
+impl<S> ContinuousNormal<Ray3<S>> for PartialCylinder<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn intersection_normal(
+        &self,
+        r: &Ray3<S>,
+    ) -> Option<(Self::Point, <Self::Point as EuclideanSpace>::Diff)> {
+        self.hit(r).map(|(t, normal)| (r.origin + r.direction * t, normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_ulps_eq;
+    use cgmath::{vec3, Rad};
+
+    use super::*;
+
+    fn quarter_wedge() -> PartialCylinder<f64> {
+        // The quarter-arc straddling the positive Z axis (45 degrees either side).
+        use ::std::f64::consts::FRAC_PI_4;
+        PartialCylinder::new(2., 1., Rad(FRAC_PI_4), Rad(3. * FRAC_PI_4))
+    }
+
+    #[test]
+    fn test_full_sweep_matches_cylinder_side_hit() {
+        let cylinder = PartialCylinder::new(2., 1., Rad(0.), Rad(2. * ::std::f64::consts::PI));
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(
+            Some((Point3::new(-1., 0., 0.), vec3(-1., 0., 0.))),
+            cylinder.intersection_normal(&ray)
+        );
+    }
+
+    #[test]
+    fn test_side_hit_inside_arc() {
+        let wedge = quarter_wedge();
+        // Passes straight through along Z; the near hit (z = -1) falls outside the arc,
+        // so the first *valid* hit is the far side at z = 1.
+        let ray = Ray3::new(Point3::new(0., 0., -3.), Vector3::new(0., 0., 1.));
+        assert_eq!(Some(Point3::new(0., 0., 1.)), wedge.intersection(&ray));
+    }
+
+    #[test]
+    fn test_side_miss_outside_arc() {
+        let wedge = quarter_wedge();
+        // This ray only ever crosses the infinite cylinder's side at phi = 0 and
+        // phi = pi, both outside the wedge's [pi/4, 3pi/4] arc.
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(None, wedge.intersection(&ray));
+    }
+
+    #[test]
+    fn test_surface_uv_at_arc_start() {
+        let wedge = quarter_wedge();
+        let phi = ::std::f64::consts::FRAC_PI_4;
+        let (u, v) = wedge.surface_uv(Point3::new(phi.cos(), -2., phi.sin()));
+        assert_ulps_eq!(0., u);
+        assert_ulps_eq!(0., v);
+    }
+
+    #[test]
+    fn test_surface_uv_at_arc_end() {
+        let wedge = quarter_wedge();
+        let phi = 3. * ::std::f64::consts::FRAC_PI_4;
+        let (u, v) = wedge.surface_uv(Point3::new(phi.cos(), 2., phi.sin()));
+        assert_ulps_eq!(1., u);
+        assert_ulps_eq!(1., v);
+    }
+}